@@ -5,22 +5,382 @@ use csv::Writer;
 use glob::glob;
 use lazy_static::lazy_static;
 use log::{debug, error, info, trace, warn};
-use lopdf::Document;
+use lopdf::{
+    content::{Content, Operation},
+    Document, Object,
+};
 use regex::Regex;
 use serde::Serialize;
+use time::{format_description, macros::format_description as const_format_description, Date};
 
 lazy_static! {
     static ref RE_DATE: Regex = Regex::new(r"\d{2}\.\d{2}\.\d{4}").unwrap();
     static ref RE_NUMBER: Regex = Regex::new(r"\d+,\d+").unwrap();
+    static ref RE_FX: Regex = Regex::new(r"([A-Z]{3})\s+(\d+,\d+)\s*\(KURS\s+(\d+,\d+)\)").unwrap();
 }
 static STARTING_TEXT: &str = "ALTER SALDO";
 static ENDING_TEXT: &str = "NEUER SALDO";
 
-#[derive(Debug, PartialEq, Serialize)]
+/// The PDF statements always render dates as German `DD.MM.YYYY`.
+const DATE_PARSE_FORMAT: &[time::format_description::FormatItem<'static>] =
+    const_format_description!("[day].[month].[year]");
+
+/// Default `--date-format`: ISO-8601.
+pub static DEFAULT_DATE_FORMAT: &str = "[year]-[month]-[day]";
+
+/// Maximum difference (in PDF user-space units) between two tokens' `y`
+/// coordinates for them to be considered part of the same row.
+const POSITION_ERROR_MARGIN: f64 = 2.0;
+
+/// `(a, b, c, d, e, f)` coefficients of a PDF text/line matrix, as used by
+/// the `Tm`/`Td`/`TD` operators. `e`/`f` hold the current translation.
+type Matrix = (f64, f64, f64, f64, f64, f64);
+const IDENTITY_MATRIX: Matrix = (1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+
+fn mat_mul(lhs: Matrix, rhs: Matrix) -> Matrix {
+    let (a1, b1, c1, d1, e1, f1) = lhs;
+    let (a2, b2, c2, d2, e2, f2) = rhs;
+    (
+        a1 * a2 + b1 * c2,
+        a1 * b2 + b1 * d2,
+        c1 * a2 + d1 * c2,
+        c1 * b2 + d1 * d2,
+        e1 * a2 + f1 * c2 + e2,
+        e1 * b2 + f1 * d2 + f2,
+    )
+}
+
+fn object_to_f64(object: &Object) -> Option<f64> {
+    match object {
+        Object::Integer(i) => Some(*i as f64),
+        Object::Real(f) => Some(*f as f64),
+        _ => None,
+    }
+}
+
+fn object_to_text(object: &Object) -> Option<String> {
+    match object {
+        Object::String(bytes, _) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        _ => None,
+    }
+}
+
+/// A single text-showing operation (`Tj`/`TJ`), positioned by the text
+/// matrix in effect when it ran.
+#[derive(Debug, Clone)]
+struct PositionedToken {
+    x: f64,
+    y: f64,
+    text: String,
+}
+
+/// Walks a page's content stream, tracking the text matrix through
+/// `BT`/`Tm`/`Td`/`TD`/`TL`/`T*`, and records one [`PositionedToken`] per
+/// `Tj`/`TJ`/`'`/`"` operation at its `(x, y)` position.
+///
+/// Text is read straight from the `Tj`/`TJ` string operands via
+/// [`object_to_text`], with no font-encoding/ToUnicode-CMap decoding
+/// (unlike `lopdf::Document::extract_text`, used by the regex-based
+/// fallback this function backs). A statement using an embedded/subset
+/// font with custom glyph codes could come out garbled rather than empty,
+/// so the "layout extraction empty ⇒ fall back to regex" safety net
+/// wouldn't catch it; so far all sample statements use a standard
+/// encoding and this hasn't been observed in practice.
+fn extract_page_tokens(document: &Document, page_num: u32) -> Result<Vec<PositionedToken>> {
+    let page_id = *document
+        .get_pages()
+        .get(&page_num)
+        .ok_or_else(|| anyhow::anyhow!("Page {} not found", page_num))?;
+    let content = Content::decode(&document.get_page_content(page_id)?)?;
+
+    Ok(tokens_from_operations(&content.operations))
+}
+
+/// The matrix-walking core of [`extract_page_tokens`], pulled out so it can
+/// be exercised with hand-built [`Operation`]s in tests without needing a
+/// full [`Document`].
+fn tokens_from_operations(operations: &[Operation]) -> Vec<PositionedToken> {
+    let mut tokens = Vec::new();
+    let mut text_matrix = IDENTITY_MATRIX;
+    let mut line_matrix = IDENTITY_MATRIX;
+    // Current leading (`TL`), i.e. the line spacing `T*`/`'`/`"` advance by.
+    let mut leading = 0.0;
+
+    for operation in operations {
+        match operation.operator.as_str() {
+            "BT" => {
+                text_matrix = IDENTITY_MATRIX;
+                line_matrix = IDENTITY_MATRIX;
+            }
+            "Tm" => {
+                let operands: Vec<f64> = operation
+                    .operands
+                    .iter()
+                    .filter_map(object_to_f64)
+                    .collect();
+                if let [a, b, c, d, e, f] = operands[..] {
+                    text_matrix = (a, b, c, d, e, f);
+                    line_matrix = text_matrix;
+                }
+            }
+            "TL" => {
+                if let Some(tl) = operation.operands.first().and_then(object_to_f64) {
+                    leading = tl;
+                }
+            }
+            "Td" | "TD" => {
+                let operands: Vec<f64> = operation
+                    .operands
+                    .iter()
+                    .filter_map(object_to_f64)
+                    .collect();
+                if let [tx, ty] = operands[..] {
+                    if operation.operator == "TD" {
+                        leading = -ty;
+                    }
+                    line_matrix = mat_mul((1.0, 0.0, 0.0, 1.0, tx, ty), line_matrix);
+                    text_matrix = line_matrix;
+                }
+            }
+            "T*" => {
+                line_matrix = mat_mul((1.0, 0.0, 0.0, 1.0, 0.0, -leading), line_matrix);
+                text_matrix = line_matrix;
+            }
+            "'" | "\"" => {
+                // Both move to the next line like `T*` before showing text;
+                // `"` additionally sets word/char spacing via its first two
+                // operands, which this extractor doesn't model, so the
+                // string to show is always the last operand.
+                line_matrix = mat_mul((1.0, 0.0, 0.0, 1.0, 0.0, -leading), line_matrix);
+                text_matrix = line_matrix;
+                if let Some(text) = operation.operands.last().and_then(object_to_text) {
+                    tokens.push(PositionedToken {
+                        x: text_matrix.4,
+                        y: text_matrix.5,
+                        text,
+                    });
+                }
+            }
+            "Tj" => {
+                if let Some(text) = operation.operands.first().and_then(object_to_text) {
+                    tokens.push(PositionedToken {
+                        x: text_matrix.4,
+                        y: text_matrix.5,
+                        text,
+                    });
+                }
+            }
+            "TJ" => {
+                if let Some(Object::Array(parts)) = operation.operands.first() {
+                    let text: String = parts.iter().filter_map(object_to_text).collect();
+                    if !text.is_empty() {
+                        tokens.push(PositionedToken {
+                            x: text_matrix.4,
+                            y: text_matrix.5,
+                            text,
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    tokens
+}
+
+/// Groups tokens into rows by `y` proximity (within [`POSITION_ERROR_MARGIN`]),
+/// top to bottom, sorting each row's tokens left to right by `x`.
+fn group_into_rows(mut tokens: Vec<PositionedToken>) -> Vec<Vec<PositionedToken>> {
+    tokens.sort_by(|a, b| b.y.partial_cmp(&a.y).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut rows: Vec<Vec<PositionedToken>> = Vec::new();
+    for token in tokens {
+        match rows.last_mut() {
+            Some(row) if (row[0].y - token.y).abs() < POSITION_ERROR_MARGIN => row.push(token),
+            _ => rows.push(vec![token]),
+        }
+    }
+
+    for row in &mut rows {
+        row.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap_or(std::cmp::Ordering::Equal));
+    }
+
+    rows
+}
+
+/// Reconstructs transactions from rows of positioned tokens: a row becomes
+/// a new transaction when it has both a leftmost date token and a rightmost
+/// amount token, otherwise it is a continuation merged into the previous
+/// transaction's description. A row with a date and an amount is dropped
+/// (not merged) when its description is empty, its amount is zero or its
+/// date does not parse.
+fn transactions_from_rows(rows: &[Vec<PositionedToken>]) -> Vec<Transaction> {
+    let mut transactions: Vec<Transaction> = Vec::new();
+
+    for row in rows {
+        let date_idx = row.iter().position(|t| RE_DATE.is_match(&t.text));
+        let amount_idx = row.iter().rposition(|t| RE_NUMBER.is_match(&t.text));
+
+        let row_text = || {
+            row.iter()
+                .map(|t| t.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        match (date_idx, amount_idx) {
+            (Some(date_idx), Some(amount_idx)) if amount_idx > date_idx => {
+                let date = row[date_idx].text.trim().to_string();
+                let description = row[date_idx + 1..amount_idx]
+                    .iter()
+                    .map(|t| t.text.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let amount = match RE_NUMBER.find(&row[amount_idx].text) {
+                    Some(m) => m
+                        .as_str()
+                        .replace(",", ".")
+                        .parse::<f64>()
+                        .unwrap_or_default(),
+                    None => 0.0,
+                };
+
+                if description.is_empty() || amount == 0.0 {
+                    trace!("Failed to process row: {:?}", row_text());
+                    continue;
+                }
+
+                let date = match Date::parse(&date, DATE_PARSE_FORMAT) {
+                    Ok(date) => date,
+                    Err(e) => {
+                        trace!("Failed to parse date {:?}: {}", date, e);
+                        continue;
+                    }
+                };
+
+                let (description, fx) = extract_foreign_currency(&description);
+                let tran = Transaction {
+                    date,
+                    description: join_description_lines(&description),
+                    amount,
+                    original_amount: fx.original_amount,
+                    original_currency: fx.original_currency,
+                    fx_rate: fx.fx_rate,
+                };
+                debug!("Found transaction: {:?}", tran);
+                transactions.push(tran);
+            }
+            _ => {
+                if let Some(previous) = transactions.last_mut() {
+                    let continuation = row_text();
+                    if !continuation.is_empty() {
+                        previous.description =
+                            format!("{}, {}", previous.description, continuation);
+                    }
+                }
+            }
+        }
+    }
+
+    transactions
+}
+
+/// Geometry-based alternative to [`get_transactions`]: reads the page's
+/// content stream operators directly instead of relying on `lopdf`'s
+/// flattened `extract_text`, so rows are reconstructed from token layout
+/// rather than from string search. Returns an empty list when nothing is
+/// found, in which case [`get_transactions_from_pdf`] falls back to
+/// [`get_transactions`], e.g. for content streams this parser can't handle.
+fn get_transactions_from_layout(document: &Document, page_num: u32) -> Result<Vec<Transaction>> {
+    let tokens = extract_page_tokens(document, page_num)?;
+
+    // Map each token to the byte offset it starts at in the tokens' joined
+    // text, so STARTING_TEXT/ENDING_TEXT (which may span several tokens)
+    // can be located the same way get_transactions_from_pdf locates them
+    // in lopdf's flattened text.
+    let mut joined = String::new();
+    let mut offsets = Vec::with_capacity(tokens.len());
+    for token in &tokens {
+        offsets.push(joined.len());
+        joined.push_str(&token.text);
+        joined.push(' ');
+    }
+
+    let token_at_offset = |offset: usize| offsets.partition_point(|&o| o <= offset).max(1) - 1;
+
+    let start = joined.find(STARTING_TEXT).map(token_at_offset).unwrap_or(0);
+    let end = joined
+        .find(ENDING_TEXT)
+        .map(token_at_offset)
+        .unwrap_or(tokens.len());
+    if end <= start {
+        return Ok(Vec::new());
+    }
+
+    let rows = group_into_rows(tokens[start..end].to_vec());
+    Ok(transactions_from_rows(&rows))
+}
+
+#[derive(Debug, PartialEq)]
 pub struct Transaction {
-    pub date: String,
+    pub date: Date,
     pub description: String,
     pub amount: f64,
+    /// Amount in [`Transaction::original_currency`], for foreign-currency
+    /// transactions like `IKEA BORLANGE - SEK 111,00 (KURS 11,1111)`.
+    pub original_amount: Option<f64>,
+    pub original_currency: Option<String>,
+    /// Exchange rate the bank applied to convert `original_amount` to `amount`.
+    pub fx_rate: Option<f64>,
+}
+
+/// Shape written to the CSV: [`Transaction::date`] rendered using the
+/// user-selected `--date-format` rather than `time::Date`'s own `Serialize`.
+#[derive(Serialize)]
+struct TransactionRecord<'a> {
+    date: String,
+    description: &'a str,
+    amount: f64,
+    original_amount: Option<f64>,
+    original_currency: Option<&'a str>,
+    fx_rate: Option<f64>,
+}
+
+#[derive(Debug, Default)]
+struct ForeignCurrency {
+    original_amount: Option<f64>,
+    original_currency: Option<String>,
+    fx_rate: Option<f64>,
+}
+
+/// Pulls the `SEK 111,00 (KURS 11,1111)`-style foreign-currency block out
+/// of a transaction description, returning the description with that block
+/// removed alongside the parsed fields. Lines are left unjoined so the
+/// caller can still trim the dash/comma leftovers line by line.
+fn extract_foreign_currency(text: &str) -> (String, ForeignCurrency) {
+    match RE_FX.captures(text) {
+        Some(caps) => {
+            let fx = ForeignCurrency {
+                original_amount: caps[2].replace(",", ".").parse().ok(),
+                original_currency: Some(caps[1].to_string()),
+                fx_rate: caps[3].replace(",", ".").parse().ok(),
+            };
+            (RE_FX.replace(text, "").into_owned(), fx)
+        }
+        None => (text.to_string(), ForeignCurrency::default()),
+    }
+}
+
+/// Joins the (possibly multi-line) description into the single comma
+/// separated line written to the CSV, trimming the dash/comma left behind
+/// on a line after [`extract_foreign_currency`] removed its FX block.
+fn join_description_lines(text: &str) -> String {
+    text.lines()
+        .map(|line| line.trim().trim_end_matches(['-', ',']).trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 fn split_by_regex<'a>(text: &'a str, re: &Regex) -> Vec<&'a str> {
@@ -82,10 +442,22 @@ fn get_transactions(text: &str) -> Vec<Transaction> {
             continue;
         }
 
+        let date = match Date::parse(date.trim(), DATE_PARSE_FORMAT) {
+            Ok(date) => date,
+            Err(e) => {
+                trace!("Failed to parse date {:?}: {}", date, e);
+                continue;
+            }
+        };
+
+        let (description, fx) = extract_foreign_currency(description);
         let tran = Transaction {
-            date: date.trim().to_string(),
-            description: description.replace("\n", ", ").trim().to_string(),
+            date,
+            description: join_description_lines(&description),
             amount,
+            original_amount: fx.original_amount,
+            original_currency: fx.original_currency,
+            fx_rate: fx.fx_rate,
         };
         debug!("Found transaction: {:?}", tran);
         transactions.push(tran);
@@ -97,6 +469,20 @@ fn get_transactions(text: &str) -> Vec<Transaction> {
 fn get_transactions_from_pdf(document: &Document) -> Vec<Transaction> {
     let mut transactions = Vec::new();
     for page_num in 1..=document.get_pages().len() {
+        let layout_transactions = match get_transactions_from_layout(document, page_num as u32) {
+            Ok(transactions) => transactions,
+            Err(e) => {
+                trace!("Layout extraction failed for page {}: {}", page_num, e);
+                Vec::new()
+            }
+        };
+        if !layout_transactions.is_empty() {
+            transactions.extend(layout_transactions);
+            continue;
+        }
+
+        // Fall back to the regex-based flat-text extraction when the
+        // layout extractor can't make sense of this page's content stream.
         match document.extract_text(&[page_num as u32]) {
             Ok(text) => {
                 let start = text.find(STARTING_TEXT).unwrap_or(0);
@@ -112,7 +498,12 @@ fn get_transactions_from_pdf(document: &Document) -> Vec<Transaction> {
     transactions
 }
 
-pub fn advanzia2csv(pdf_or_folder: &Path, csv_file: &Path) -> Result<()> {
+pub fn advanzia2csv(
+    pdf_or_folder: &Path,
+    csv_file: &Path,
+    swap_sign: bool,
+    date_format: &str,
+) -> Result<()> {
     let paths = if pdf_or_folder.is_dir() {
         glob(&format!("{}/**/*.pdf", pdf_or_folder.display()))?
             .filter_map(Result::ok)
@@ -121,7 +512,7 @@ pub fn advanzia2csv(pdf_or_folder: &Path, csv_file: &Path) -> Result<()> {
         vec![pdf_or_folder.to_path_buf()]
     };
 
-    let transactions: Vec<Transaction> = paths
+    let mut transactions: Vec<Transaction> = paths
         .iter()
         .flat_map(|pdf_path| match Document::load(pdf_path) {
             Ok(document) => {
@@ -142,16 +533,35 @@ pub fn advanzia2csv(pdf_or_folder: &Path, csv_file: &Path) -> Result<()> {
         ));
     }
 
+    // Sort chronologically, which also interleaves transactions from
+    // multiple merged statement PDFs into a single timeline.
+    transactions.sort_by_key(|t| t.date);
+
+    if swap_sign {
+        for transaction in &mut transactions {
+            transaction.amount = -transaction.amount;
+        }
+    }
+
     info!(
         "{} transactions saved to {}",
         transactions.len(),
         csv_file.display()
     );
 
+    let date_format = format_description::parse_borrowed::<2>(date_format)?;
     let file = File::create(csv_file)?;
     let mut writer = Writer::from_writer(file);
 
-    for record in transactions {
+    for transaction in &transactions {
+        let record = TransactionRecord {
+            date: transaction.date.format(&date_format)?,
+            description: &transaction.description,
+            amount: transaction.amount,
+            original_amount: transaction.original_amount,
+            original_currency: transaction.original_currency.as_deref(),
+            fx_rate: transaction.fx_rate,
+        };
         writer.serialize(record)?;
     }
     writer.flush()?;
@@ -162,6 +572,85 @@ pub fn advanzia2csv(pdf_or_folder: &Path, csv_file: &Path) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn test_tokens_from_operations() {
+        // BT, position at (0, 100) via Tm, show "26.01.2021", set leading
+        // to 10 via TL, advance a line with T* (should land at y=90, not
+        // stay at y=100 like a no-op T* would), then show "BORLANGE".
+        let operations = vec![
+            Operation::new("BT", vec![]),
+            Operation::new(
+                "Tm",
+                vec![
+                    Object::Real(1.0),
+                    Object::Real(0.0),
+                    Object::Real(0.0),
+                    Object::Real(1.0),
+                    Object::Real(0.0),
+                    Object::Real(100.0),
+                ],
+            ),
+            Operation::new("Tj", vec![Object::string_literal("26.01.2021")]),
+            Operation::new("TL", vec![Object::Real(10.0)]),
+            Operation::new("T*", vec![]),
+            Operation::new("Tj", vec![Object::string_literal("BORLANGE")]),
+        ];
+
+        let tokens = tokens_from_operations(&operations);
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].x, 0.0);
+        assert_eq!(tokens[0].y, 100.0);
+        assert_eq!(tokens[0].text, "26.01.2021");
+        assert_eq!(tokens[1].x, 0.0);
+        assert_eq!(tokens[1].y, 90.0);
+        assert_eq!(tokens[1].text, "BORLANGE");
+    }
+
+    #[test]
+    fn test_tokens_from_operations_td_sets_leading() {
+        // TD moves like Td but also sets leading to -ty, so a later T*
+        // should reuse that same step rather than staying put.
+        let operations = vec![
+            Operation::new("BT", vec![]),
+            Operation::new("TD", vec![Object::Real(5.0), Object::Real(-12.0)]),
+            Operation::new("Tj", vec![Object::string_literal("first")]),
+            Operation::new("T*", vec![]),
+            Operation::new("Tj", vec![Object::string_literal("second")]),
+        ];
+
+        let tokens = tokens_from_operations(&operations);
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].y, -12.0);
+        assert_eq!(tokens[1].y, -24.0);
+    }
+
+    #[test]
+    fn test_tokens_from_operations_quote_operators() {
+        // `'` moves to the next line then shows text; `"` does the same
+        // but with two leading spacing operands before the string.
+        let operations = vec![
+            Operation::new("BT", vec![]),
+            Operation::new("TL", vec![Object::Real(10.0)]),
+            Operation::new("'", vec![Object::string_literal("first")]),
+            Operation::new(
+                "\"",
+                vec![
+                    Object::Real(0.0),
+                    Object::Real(0.0),
+                    Object::string_literal("second"),
+                ],
+            ),
+        ];
+
+        let tokens = tokens_from_operations(&operations);
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].y, -10.0);
+        assert_eq!(tokens[0].text, "first");
+        assert_eq!(tokens[1].y, -20.0);
+        assert_eq!(tokens[1].text, "second");
+    }
 
     #[test]
     fn test_get_transactions() {
@@ -182,25 +671,103 @@ UPPLANDS VAS
         assert_eq!(
             transactions[0],
             Transaction {
-                date: "26.01.2021".to_string(),
-                description: "IKEA BORLANGE - SEK 111,00 (KURS 11,1111), BORLANGE".to_string(),
+                date: date!(2021 - 01 - 26),
+                description: "IKEA BORLANGE, BORLANGE".to_string(),
                 amount: 18.30,
+                original_amount: Some(111.00),
+                original_currency: Some("SEK".to_string()),
+                fx_rate: Some(11.1111),
             }
         );
         assert_eq!(
             transactions[1],
             Transaction {
-                date: "27.02.2022".to_string(),
-                description: "FABRIQUE - SEK 1111,00 (KURS 11,1111), STOCKHOLM".to_string(),
+                date: date!(2022 - 02 - 27),
+                description: "FABRIQUE, STOCKHOLM".to_string(),
                 amount: 19.23,
+                original_amount: Some(1111.00),
+                original_currency: Some("SEK".to_string()),
+                fx_rate: Some(11.1111),
             }
         );
         assert_eq!(
             transactions[2],
             Transaction {
-                date: "27.11.2023".to_string(),
-                description: "Inc. - SEK 111,11 (KURS 11,1111), UPPLANDS VAS".to_string(),
+                date: date!(2023 - 11 - 27),
+                description: "Inc., UPPLANDS VAS".to_string(),
                 amount: 14.62,
+                original_amount: Some(111.11),
+                original_currency: Some("SEK".to_string()),
+                fx_rate: Some(11.1111),
+            }
+        );
+    }
+
+    #[test]
+    fn test_transactions_from_rows() {
+        // Simulates a wrapped description: "BORLANGE" lands on its own row
+        // below the date/amount row, as it would if lopdf's flat text put
+        // the continuation on the wrong line.
+        let rows = group_into_rows(vec![
+            PositionedToken {
+                x: 0.0,
+                y: 100.0,
+                text: "26.01.2021".to_string(),
+            },
+            PositionedToken {
+                x: 50.0,
+                y: 100.0,
+                text: "IKEA BORLANGE - SEK 111,00 (KURS 11,1111)".to_string(),
+            },
+            PositionedToken {
+                x: 300.0,
+                y: 100.0,
+                text: "18,30".to_string(),
+            },
+            PositionedToken {
+                x: 50.0,
+                y: 90.0,
+                text: "BORLANGE".to_string(),
+            },
+            PositionedToken {
+                x: 0.0,
+                y: 80.0,
+                text: "27.02.2022".to_string(),
+            },
+            PositionedToken {
+                x: 50.0,
+                y: 80.0,
+                text: "FABRIQUE - SEK 1111,00 (KURS 11,1111)".to_string(),
+            },
+            PositionedToken {
+                x: 300.0,
+                y: 80.0,
+                text: "19,23".to_string(),
+            },
+        ]);
+
+        let transactions = transactions_from_rows(&rows);
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(
+            transactions[0],
+            Transaction {
+                date: date!(2021 - 01 - 26),
+                description: "IKEA BORLANGE, BORLANGE".to_string(),
+                amount: 18.30,
+                original_amount: Some(111.00),
+                original_currency: Some("SEK".to_string()),
+                fx_rate: Some(11.1111),
+            }
+        );
+        assert_eq!(
+            transactions[1],
+            Transaction {
+                date: date!(2022 - 02 - 27),
+                description: "FABRIQUE".to_string(),
+                amount: 19.23,
+                original_amount: Some(1111.00),
+                original_currency: Some("SEK".to_string()),
+                fx_rate: Some(11.1111),
             }
         );
     }
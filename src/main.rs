@@ -1,8 +1,13 @@
-use std::{fmt, path::PathBuf};
+use std::{
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
 
 use advanzia2csv::advanzia2csv;
 use anyhow::Result;
-use clap::{command, Parser, ValueEnum};
+use clap::{Parser, ValueEnum};
 use fern::colors::{Color, ColoredLevelConfig};
 use log::LevelFilter;
 
@@ -48,12 +53,160 @@ struct Args {
     /// Swap sign of the amount
     #[arg(long, default_value_t = false)]
     swap_sign: bool,
+    /// Date format used in the output CSV, using `time`'s format
+    /// description syntax (e.g. `[day].[month].[year]` for the original
+    /// German format), defaults to ISO-8601
+    #[arg(long, default_value_t = advanzia2csv::DEFAULT_DATE_FORMAT.to_string())]
+    date_format: String,
     /// Log level
     #[arg(short, long, default_value_t = LogLevel::Info)]
     log_level: LogLevel,
+    /// Also log to this file (ANSI colors stripped), rotating it before a
+    /// write would push it past --log-file-max-bytes
+    #[arg(long)]
+    log_file: Option<PathBuf>,
+    /// Size in bytes at which --log-file is rotated
+    #[arg(long, default_value_t = 64 * 1024)]
+    log_file_max_bytes: u64,
+    /// Number of rotated --log-file backups to keep
+    #[arg(long, default_value_t = 5)]
+    log_file_max_backups: usize,
 }
 
-fn setup_logger(log_level: LevelFilter) -> Result<()> {
+/// A log file sink that renames the current file with a timestamp suffix
+/// and starts a fresh one before a write would push it past `max_bytes`,
+/// keeping at most
+/// `max_backups` rotated files around.
+struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    max_backups: usize,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf, max_bytes: u64, max_backups: usize) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            max_bytes,
+            max_backups,
+            file,
+            size,
+        })
+    }
+
+    /// `path`'s file name, as a clean `io::Error` (not a panic) when it has
+    /// none, e.g. `--log-file .`, `--log-file ..` or `--log-file /`.
+    fn file_name(path: &Path) -> io::Result<&std::ffi::OsStr> {
+        path.file_name().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("--log-file {:?} has no file name", path),
+            )
+        })
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        let suffix = chrono::Local::now().format("%Y-%m-%d-%H:%M:%S%.3f");
+        let rotated = self.path.with_file_name(format!(
+            "{}.{}",
+            Self::file_name(&self.path)?.to_string_lossy(),
+            suffix
+        ));
+        fs::rename(&self.path, rotated)?;
+
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = 0;
+
+        self.prune_backups()
+    }
+
+    fn prune_backups(&self) -> io::Result<()> {
+        let dir = self.path.parent().filter(|p| !p.as_os_str().is_empty());
+        let file_name = Self::file_name(&self.path)?.to_string_lossy();
+        let prefix = format!("{}.", file_name);
+
+        let mut backups: Vec<PathBuf> = fs::read_dir(dir.unwrap_or_else(|| Path::new(".")))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.file_name()
+                    .map(|name| name.to_string_lossy().starts_with(&prefix))
+                    .unwrap_or(false)
+            })
+            .collect();
+        backups.sort();
+
+        let keep = self.max_backups.min(backups.len());
+        for oldest in &backups[..backups.len() - keep] {
+            fs::remove_file(oldest)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Wraps a writer, stripping ANSI color escape sequences (as produced by
+/// `fern::colors::ColoredLevelConfig`) before the bytes reach it, so a
+/// log file stays plain text and greppable.
+struct StripAnsi<W> {
+    inner: W,
+}
+
+impl<W: Write> Write for StripAnsi<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut stripped = Vec::with_capacity(buf.len());
+        let mut bytes = buf.iter().copied().peekable();
+        while let Some(byte) = bytes.next() {
+            if byte == 0x1b && bytes.peek() == Some(&b'[') {
+                // CSI escape sequence: ESC '[' ... final byte in 0x40..=0x7e.
+                // The '[' itself falls in that range, so it must be
+                // consumed before scanning for the real terminator.
+                bytes.next();
+                for b in bytes.by_ref() {
+                    if (0x40..=0x7e).contains(&b) {
+                        break;
+                    }
+                }
+                continue;
+            }
+            stripped.push(byte);
+        }
+        self.inner.write_all(&stripped)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn setup_logger(
+    log_level: LevelFilter,
+    log_file: Option<PathBuf>,
+    log_file_max_bytes: u64,
+    log_file_max_backups: usize,
+) -> Result<()> {
     let colors = ColoredLevelConfig::new()
         .error(Color::Red)
         .warn(Color::Yellow)
@@ -61,7 +214,7 @@ fn setup_logger(log_level: LevelFilter) -> Result<()> {
         .debug(Color::Cyan)
         .trace(Color::Magenta);
 
-    fern::Dispatch::new()
+    let mut dispatch = fern::Dispatch::new()
         .format(move |out, message, record| {
             out.finish(format_args!(
                 "[{}][{}][{}] {}",
@@ -73,14 +226,112 @@ fn setup_logger(log_level: LevelFilter) -> Result<()> {
         })
         .level(log::LevelFilter::Warn) // Set the default level
         .level_for(module_path!(), log_level) // Set the default level
-        .chain(std::io::stdout())
-        .apply()?;
+        .chain(std::io::stdout());
+
+    if let Some(log_file) = log_file {
+        let file = RotatingFile::open(log_file, log_file_max_bytes, log_file_max_backups)?;
+        dispatch = dispatch.chain(Box::new(StripAnsi { inner: file }) as Box<dyn Write + Send>);
+    }
+
+    dispatch.apply()?;
 
     Ok(())
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
-    setup_logger(args.log_level.into())?;
-    advanzia2csv(&args.input, &args.output, args.swap_sign)
+    setup_logger(
+        args.log_level.into(),
+        args.log_file,
+        args.log_file_max_bytes,
+        args.log_file_max_backups,
+    )?;
+    advanzia2csv(&args.input, &args.output, args.swap_sign, &args.date_format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A directory under `std::env::temp_dir()` that removes itself on drop,
+    /// so each test gets an isolated place to rotate/prune real files.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "advanzia2csv-test-{}-{}",
+                name,
+                std::process::id()
+            ));
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn path(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_rotating_file_rotates_at_byte_threshold() {
+        let dir = TempDir::new("rotate");
+        let log_path = dir.path().join("app.log");
+        let mut file = RotatingFile::open(log_path.clone(), 10, 5).unwrap();
+
+        file.write_all(b"12345").unwrap();
+        assert_eq!(fs::read_to_string(&log_path).unwrap(), "12345");
+
+        // This write would push the file past max_bytes, so it should
+        // rotate the existing content out before writing the new bytes.
+        file.write_all(b"678901").unwrap();
+        assert_eq!(fs::read_to_string(&log_path).unwrap(), "678901");
+
+        let rotated: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with("app.log."))
+            .collect();
+        assert_eq!(rotated.len(), 1);
+        assert_eq!(fs::read_to_string(rotated[0].path()).unwrap(), "12345");
+    }
+
+    #[test]
+    fn test_rotating_file_prunes_backups_keeping_only_max() {
+        let dir = TempDir::new("prune");
+        let log_path = dir.path().join("app.log");
+        let mut file = RotatingFile::open(log_path.clone(), 1, 2).unwrap();
+
+        for i in 0..4 {
+            file.write_all(format!("{}", i).as_bytes()).unwrap();
+            // Rotated files are suffixed with a millisecond timestamp;
+            // space writes out so consecutive rotations don't collide.
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+
+        let backups: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with("app.log."))
+            .collect();
+        assert_eq!(backups.len(), 2);
+    }
+
+    #[test]
+    fn test_strip_ansi_removes_color_codes() {
+        let mut out = Vec::new();
+        {
+            let mut writer = StripAnsi { inner: &mut out };
+            writer
+                .write_all(b"\x1b[32m[INFO]\x1b[0m hello world")
+                .unwrap();
+        }
+        assert_eq!(out, b"[INFO] hello world");
+    }
 }